@@ -0,0 +1,14 @@
+//! Kernel object error codes, mirroring `zx_status_t`.
+
+/// Error codes returned by kernel object operations.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ZxError {
+    OK,
+    NOT_FOUND,
+    TIMED_OUT,
+    BAD_STATE,
+    ALREADY_BOUND,
+}
+
+/// A `Result` alias used throughout the kernel object layer; defaults to `()` on success.
+pub type ZxResult<T = ()> = Result<T, ZxError>;
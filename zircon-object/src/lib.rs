@@ -0,0 +1,13 @@
+//! Zircon kernel object primitives.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+pub mod error;
+pub mod object;
+pub mod signal;
+
+pub use error::*;
@@ -0,0 +1,225 @@
+//! Shared kernel object behavior: identity, signal state, and asynchronous waits.
+
+use crate::signal::{Port, Signal, WaitAsyncOpts};
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::task::{Context, Poll, Waker};
+use spin::Mutex;
+
+/// Common behavior implemented by every kernel object: identity, signal state, and
+/// binding to ports. Concrete dispatchers implement this via [`impl_kobject!`].
+pub trait KernelObject: Send + Sync {
+    /// The object's kernel-assigned id.
+    fn id(&self) -> u64;
+    /// A human-readable type name, used in debug output.
+    fn type_name(&self) -> &'static str;
+    /// Access the object's shared signal/waiter state.
+    fn base(&self) -> &KObjectBase;
+
+    /// Asynchronous wait until any bit in `signal` becomes active.
+    fn wait_signal_async(self: Arc<Self>, signal: Signal) -> SignalWait;
+
+    /// The signals currently active on this object.
+    fn signal(&self) -> Signal {
+        self.base().signal()
+    }
+
+    /// Assert `signal`: wakes pending [`wait_signal_async`](Self::wait_signal_async)
+    /// futures and notifies every port this object is bound to, via
+    /// [`send_signal_to_port_async`](Self::send_signal_to_port_async).
+    fn signal_set(&self, signal: Signal) {
+        self.base().signal_set(signal);
+    }
+
+    /// Deassert `signal`.
+    fn signal_clear(&self, signal: Signal) {
+        self.base().signal_clear(signal);
+    }
+
+    /// Register an `object_wait_async` subscription, binding this object to `port`
+    /// under `key`: whenever this object's active signals intersect `trigger`, `port`
+    /// is notified. `opts` controls whether the subscription fires once or repeatedly.
+    fn send_signal_to_port_async(
+        &self,
+        trigger: Signal,
+        port: &Arc<Port>,
+        key: u64,
+        opts: WaitAsyncOpts,
+    ) {
+        if let Some(object) = self.base().arc_self() {
+            port.push_async_wait(&object, key, trigger, opts);
+        }
+        self.base().bind_port(port);
+    }
+}
+
+/// Shared signal/waiter state embedded in every kernel object.
+pub struct KObjectBase {
+    /// The object's kernel-assigned id.
+    pub id: u64,
+    inner: Mutex<KObjectBaseInner>,
+}
+
+#[derive(Default)]
+struct KObjectBaseInner {
+    signal: Signal,
+    waiters: Vec<Waker>,
+    bound_ports: Vec<Weak<Port>>,
+    weak_self: Option<Weak<dyn KernelObject>>,
+}
+
+impl KObjectBase {
+    /// Create a new base for an object, remembering `weak_self` so `signal_set` can
+    /// later hand bound ports a strong reference without requiring an `Arc<Self>`
+    /// receiver on every object method.
+    pub fn new(weak_self: Weak<dyn KernelObject>) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        KObjectBase {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            inner: Mutex::new(KObjectBaseInner {
+                weak_self: Some(weak_self),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// The signals currently active.
+    pub fn signal(&self) -> Signal {
+        self.inner.lock().signal
+    }
+
+    /// Assert `signal`, waking pending waiters and notifying bound ports.
+    pub fn signal_set(&self, signal: Signal) {
+        let mut inner = self.inner.lock();
+        let old = inner.signal;
+        inner.signal.insert(signal);
+        let cur = inner.signal;
+        let waiters = core::mem::take(&mut inner.waiters);
+        let object = if cur != old {
+            inner.weak_self.clone().and_then(|w| w.upgrade())
+        } else {
+            None
+        };
+        let bound_ports = inner.bound_ports.clone();
+        drop(inner);
+
+        for waker in waiters {
+            waker.wake();
+        }
+        if let Some(object) = object {
+            for port in bound_ports.iter().filter_map(Weak::upgrade) {
+                port.on_signal_change(&object, cur);
+            }
+        }
+    }
+
+    /// Deassert `signal`.
+    pub fn signal_clear(&self, signal: Signal) {
+        self.inner.lock().signal.remove(signal);
+    }
+
+    /// Register a waker to be woken the next time any signal is asserted.
+    pub(crate) fn register_waiter(&self, waker: Waker) {
+        self.inner.lock().waiters.push(waker);
+    }
+
+    /// Remember that `port` is bound to this object, so future `signal_set` calls
+    /// notify it.
+    pub(crate) fn bind_port(&self, port: &Arc<Port>) {
+        let mut inner = self.inner.lock();
+        if !inner.bound_ports.iter().any(|p| p.as_ptr() == Arc::as_ptr(port)) {
+            inner.bound_ports.push(Arc::downgrade(port));
+        }
+    }
+
+    /// Upgrade the object's own weak self-reference, if it is still alive.
+    pub(crate) fn arc_self(&self) -> Option<Arc<dyn KernelObject>> {
+        self.inner.lock().weak_self.clone().and_then(|w| w.upgrade())
+    }
+}
+
+/// Future returned by [`KernelObject::wait_signal_async`].
+pub struct SignalWait {
+    object: Arc<dyn KernelObject>,
+    signal: Signal,
+}
+
+impl SignalWait {
+    /// Build a `SignalWait` for `object`'s `signal`, type-erasing `object` up front so
+    /// the future itself doesn't need to be generic.
+    pub fn new<O: KernelObject + 'static>(object: Arc<O>, signal: Signal) -> Self {
+        SignalWait {
+            object: object as Arc<dyn KernelObject>,
+            signal,
+        }
+    }
+}
+
+impl Future for SignalWait {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.object.signal().intersects(self.signal) {
+            Poll::Ready(())
+        } else {
+            self.object.base().register_waiter(cx.waker().clone());
+            // Check again: a signal asserted between the first check and the
+            // registration would otherwise never wake this future.
+            if self.object.signal().intersects(self.signal) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Implement [`KernelObject`] for a type with a `base: KObjectBase` field.
+#[macro_export]
+macro_rules! impl_kobject {
+    ($class:ident) => {
+        impl $crate::object::KernelObject for $class {
+            fn id(&self) -> u64 {
+                self.base.id
+            }
+
+            fn type_name(&self) -> &'static str {
+                stringify!($class)
+            }
+
+            fn base(&self) -> &$crate::object::KObjectBase {
+                &self.base
+            }
+
+            fn wait_signal_async(
+                self: alloc::sync::Arc<Self>,
+                signal: $crate::signal::Signal,
+            ) -> $crate::object::SignalWait {
+                $crate::object::SignalWait::new(self, signal)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_kobject;
+
+/// A minimal kernel object used only by this crate's own tests.
+#[cfg(test)]
+pub struct DummyObject {
+    base: KObjectBase,
+}
+
+#[cfg(test)]
+impl_kobject!(DummyObject);
+
+#[cfg(test)]
+impl DummyObject {
+    pub fn new() -> Arc<Self> {
+        Arc::new_cyclic(|weak| DummyObject {
+            base: KObjectBase::new(weak.clone() as Weak<dyn KernelObject>),
+        })
+    }
+}
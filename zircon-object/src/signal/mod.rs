@@ -0,0 +1,17 @@
+//! Signal bits and the port mailbox used to deliver them.
+
+use bitflags::bitflags;
+
+mod port;
+
+pub use self::port::*;
+pub use crate::error::*;
+
+bitflags! {
+    /// Signal bits observed on kernel objects and delivered through bound ports.
+    #[derive(Default)]
+    pub struct Signal: u32 {
+        const READABLE = 1 << 0;
+        const WRITABLE = 1 << 1;
+    }
+}
@@ -1,7 +1,13 @@
 use super::*;
 use crate::object::*;
-use alloc::sync::Arc;
+use alloc::collections::BinaryHeap;
+use alloc::sync::{Arc, Weak};
 use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::time::Duration;
+use futures::future::{select, Either};
+use futures::pin_mut;
+use kernel_hal::sleep_until;
 use spin::Mutex;
 
 /// Signaling and mailbox primitive
@@ -10,8 +16,10 @@ use spin::Mutex;
 ///
 /// Ports allow threads to wait for packets to be delivered from various
 /// events. These events include explicit queueing on the port,
-/// asynchronous waits on other handles bound to the port, and
-/// asynchronous message delivery from IPC transports.
+/// asynchronous waits on other handles bound to the port (one-shot or
+/// repeating, see [`WaitAsyncOpts`]), asynchronous message delivery from
+/// IPC transports, guest VM exit traps bound to the hypervisor, and
+/// hardware interrupts bound to a driver thread's mailbox.
 pub struct Port {
     base: KObjectBase,
     inner: Mutex<PortInner>,
@@ -21,28 +29,145 @@ impl_kobject!(Port);
 
 #[derive(Default)]
 struct PortInner {
-    queue: Vec<PortPacket>,
+    /// Packets waiting to be dequeued, ordered by priority and then FIFO.
+    queue: BinaryHeap<QueuedPacket>,
+    /// Registered `object_wait_async` subscriptions, checked whenever a bound
+    /// object's signal state changes.
+    async_waits: Vec<AsyncWait>,
+    /// Monotonically increasing counter used to break priority ties in FIFO order.
+    next_sequence: u64,
 }
 
+impl PortInner {
+    /// Assign the next sequence number and enqueue `packet`, optionally scoped to the
+    /// object that produced it.
+    fn enqueue(&mut self, packet: PortPacket, source: Option<Weak<dyn KernelObject>>) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.queue.push(QueuedPacket {
+            packet,
+            sequence,
+            source,
+        });
+    }
+}
+
+/// A packet together with the insertion order used to break priority ties.
+struct QueuedPacket {
+    packet: PortPacket,
+    sequence: u64,
+    /// The object this packet was generated on behalf of, if any. Two objects may be
+    /// bound to the same port under the same key (keys are caller-chosen and only need
+    /// to be unique per-binding, not per-port), so this is required to scope `cancel`
+    /// and signal coalescing to the right object instead of matching on `key` alone.
+    source: Option<Weak<dyn KernelObject>>,
+}
+
+impl PartialEq for QueuedPacket {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedPacket {}
+
+impl PartialOrd for QueuedPacket {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedPacket {
+    /// Higher priority sorts greater (popped first by the max-heap); ties break in favor
+    /// of the earlier sequence number, so `BinaryHeap::pop` yields a stable priority queue.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.packet
+            .priority
+            .cmp(&other.packet.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// A registered asynchronous signal subscription bound to this port.
+struct AsyncWait {
+    object: Weak<dyn KernelObject>,
+    key: u64,
+    trigger: Signal,
+    opts: WaitAsyncOpts,
+}
+
+/// Whether an `object_wait_async` subscription fires once or keeps re-arming.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum WaitAsyncOpts {
+    /// The packet is queued once, then the subscription is removed.
+    Once,
+    /// The packet is re-queued every time the trigger signals are asserted.
+    Repeating,
+}
+
+/// Background / bulk priority, delivered after all higher-priority packets.
+pub const PRIO_BACKGROUND: u8 = 0;
+/// Default priority used by existing call sites.
+pub const PRIO_NORMAL: u8 = 1;
+/// Latency-sensitive priority, delivered ahead of normal and background packets.
+pub const PRIO_HIGH: u8 = 2;
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct PortPacket {
     pub key: u64,
     pub status: ZxError,
     pub data: PortPacketPayload,
+    /// One of `PRIO_HIGH`, `PRIO_NORMAL`, or `PRIO_BACKGROUND`; higher-priority packets
+    /// are dequeued before lower-priority ones, breaking ties by arrival order.
+    pub priority: u8,
 }
 
 #[non_exhaustive]
 #[derive(Debug, Eq, PartialEq)]
 pub enum PortPacketPayload {
-    Signal(Signal),
+    Signal {
+        /// The signal mask the subscription was armed with.
+        trigger: Signal,
+        /// The signals observed to be active at the time(s) this packet was queued.
+        observed: Signal,
+        /// How many times the trigger condition was observed while this packet sat in
+        /// the queue, so bursts of repeating waits don't grow the queue unbounded.
+        count: u64,
+    },
     User([u8; 32]),
+    /// A guest VM exit trapped on an MMIO bell write.
+    GuestBell {
+        /// The trapped guest-physical address.
+        addr: u64,
+    },
+    /// A guest VM exit trapped on an MMIO access requiring emulation.
+    GuestMem {
+        /// The trapped guest-physical address.
+        addr: u64,
+        /// The raw trapping instruction bytes, for the VMM to decode and emulate.
+        inst: [u8; 16],
+    },
+    /// A guest VM exit trapped on a port I/O access.
+    GuestIo {
+        /// The trapped guest I/O port number.
+        port: u16,
+        /// The size in bytes of the access.
+        access_size: u8,
+        /// The I/O data, valid up to `access_size` bytes.
+        data: [u8; 4],
+    },
+    /// A hardware interrupt firing, bound to this port via the interrupt object.
+    Interrupt {
+        /// The timestamp at which the interrupt was captured.
+        timestamp: u64,
+    },
 }
 
 impl Port {
     /// Create a new `Port`.
     pub fn new() -> Arc<Self> {
-        Arc::new(Port {
-            base: KObjectBase::default(),
+        Arc::new_cyclic(|weak| Port {
+            base: KObjectBase::new(weak.clone() as Weak<dyn KernelObject>),
             inner: Mutex::default(),
         })
     }
@@ -50,11 +175,134 @@ impl Port {
     /// Push a `packet` into the port.
     pub fn push(&self, packet: PortPacket) {
         let mut inner = self.inner.lock();
-        inner.queue.push(packet);
+        inner.enqueue(packet, None);
         drop(inner);
         self.base.signal_set(Signal::READABLE);
     }
 
+    /// Queue a guest trap packet (`GuestBell` / `GuestMem` / `GuestIo`) under `key`.
+    ///
+    /// Called by the guest-physical-address-space / vCPU code when a trapped access
+    /// occurs, so a VMM thread blocked in [`wait_async_one`](Self::wait_async_one) can
+    /// service it alongside other events on the same port.
+    pub fn push_guest_trap(&self, key: u64, payload: PortPacketPayload) {
+        self.push(PortPacket {
+            key,
+            status: ZxError::OK,
+            priority: PRIO_NORMAL,
+            data: payload,
+        });
+    }
+
+    /// Queue an `Interrupt` packet under `key`, captured at `timestamp`.
+    ///
+    /// Called by an interrupt object's registered handler when the underlying IRQ
+    /// fires, so a driver thread blocked in [`wait_async_one`](Self::wait_async_one)
+    /// on this port's mailbox is woken with the capture time. Interrupt delivery is
+    /// latency-sensitive, so these packets jump the queue ahead of normal-priority work.
+    pub fn push_interrupt(&self, key: u64, timestamp: u64) {
+        self.push(PortPacket {
+            key,
+            status: ZxError::OK,
+            priority: PRIO_HIGH,
+            data: PortPacketPayload::Interrupt { timestamp },
+        });
+    }
+
+    /// Register an `object_wait_async` subscription: whenever `object`'s active signals
+    /// intersect `trigger`, a `Signal` packet carrying the observed signals is queued
+    /// under `key`. `opts` controls whether the subscription is consumed after firing
+    /// once or stays armed for every new assertion edge.
+    pub fn push_async_wait(
+        &self,
+        object: &Arc<dyn KernelObject>,
+        key: u64,
+        trigger: Signal,
+        opts: WaitAsyncOpts,
+    ) {
+        self.inner.lock().async_waits.push(AsyncWait {
+            object: Arc::downgrade(object),
+            key,
+            trigger,
+            opts,
+        });
+    }
+
+    /// Notify the port that `object`'s active signals changed to `new_signal`, so any
+    /// matching registered waits can be delivered. Called from `KObjectBase::signal_set`
+    /// for every port an object is bound to.
+    ///
+    /// If a `Signal` packet for the same `(object, key)` pair is already queued and
+    /// undelivered, the new observation is coalesced into it (OR-ing in `observed` and
+    /// bumping `count`) instead of growing the queue, so a burst of repeating waits
+    /// can't overflow the port. Matching by key alone would risk folding one object's
+    /// edge into a different object's pending packet when two objects share a key.
+    pub fn on_signal_change(&self, object: &Arc<dyn KernelObject>, new_signal: Signal) {
+        let weak = Arc::downgrade(object);
+        let mut inner = self.inner.lock();
+        let mut fired = Vec::new();
+        inner.async_waits.retain(|wait| {
+            if !Weak::ptr_eq(&wait.object, &weak) {
+                return true;
+            }
+            let observed = wait.trigger & new_signal;
+            if observed.is_empty() {
+                return true;
+            }
+            fired.push((wait.key, wait.trigger, observed));
+            wait.opts == WaitAsyncOpts::Repeating
+        });
+        if !fired.is_empty() {
+            // `BinaryHeap` has no `iter_mut`, so coalesce against a plain `Vec` and
+            // rebuild the heap once all fired waits have been folded in.
+            let mut items: Vec<QueuedPacket> = core::mem::take(&mut inner.queue).into_vec();
+            for (key, trigger, observed) in fired {
+                let coalesced = items.iter_mut().any(|item| {
+                    let same_source = item
+                        .source
+                        .as_ref()
+                        .is_some_and(|source| Weak::ptr_eq(source, &weak));
+                    if item.packet.key != key || !same_source {
+                        return false;
+                    }
+                    if let PortPacketPayload::Signal {
+                        observed: pending,
+                        count,
+                        ..
+                    } = &mut item.packet.data
+                    {
+                        *pending |= observed;
+                        *count += 1;
+                        true
+                    } else {
+                        false
+                    }
+                });
+                if !coalesced {
+                    let sequence = inner.next_sequence;
+                    inner.next_sequence += 1;
+                    items.push(QueuedPacket {
+                        packet: PortPacket {
+                            key,
+                            status: ZxError::OK,
+                            priority: PRIO_NORMAL,
+                            data: PortPacketPayload::Signal {
+                                trigger,
+                                observed,
+                                count: 1,
+                            },
+                        },
+                        sequence,
+                        source: Some(weak.clone()),
+                    });
+                }
+            }
+            inner.queue = items.into();
+            drop(inner);
+            self.base.signal_set(Signal::READABLE);
+        }
+    }
+
     /// Asynchronous wait until at least one packet is available, then take out all packets.
     pub async fn wait_async(self: &Arc<Self>) -> Vec<PortPacket> {
         (self.clone() as Arc<dyn KernelObject>)
@@ -62,7 +310,83 @@ impl Port {
             .await;
         let mut inner = self.inner.lock();
         self.base.signal_clear(Signal::READABLE);
-        core::mem::take(&mut inner.queue)
+        let mut items: Vec<QueuedPacket> = core::mem::take(&mut inner.queue).into_vec();
+        items.sort_by(|a, b| b.cmp(a));
+        items.into_iter().map(|item| item.packet).collect()
+    }
+
+    /// Asynchronous wait until `deadline`, dequeuing exactly one packet: the
+    /// highest-priority one, in FIFO order among packets of equal priority.
+    ///
+    /// This matches `zx_port_wait`: unlike [`wait_async`](Self::wait_async), it never drains
+    /// the whole queue, so multiple queued packets are delivered one per call. Returns
+    /// `ZxError::TIMED_OUT` if no packet arrives before `deadline`.
+    pub async fn wait_async_one(self: &Arc<Self>, deadline: Duration) -> ZxResult<PortPacket> {
+        loop {
+            if let Some(packet) = self.pop_one() {
+                return Ok(packet);
+            }
+            let wait = (self.clone() as Arc<dyn KernelObject>).wait_signal_async(Signal::READABLE);
+            let timeout = sleep_until(deadline);
+            pin_mut!(wait);
+            pin_mut!(timeout);
+            match select(wait, timeout).await {
+                Either::Left(_) => continue,
+                Either::Right(_) => return Err(ZxError::TIMED_OUT),
+            }
+        }
+    }
+
+    /// Pop the highest-priority packet (FIFO among equal priorities), clearing `READABLE`
+    /// only once the queue is empty.
+    fn pop_one(&self) -> Option<PortPacket> {
+        let mut inner = self.inner.lock();
+        let packet = inner.queue.pop().map(|item| item.packet);
+        if inner.queue.is_empty() {
+            self.base.signal_clear(Signal::READABLE);
+        }
+        packet
+    }
+
+    /// Remove a still-pending `object_wait_async` registration for `(object, key)`, and
+    /// drain any already-queued but not-yet-dequeued `Signal` packets matching that same
+    /// `(object, key)` pair.
+    ///
+    /// Mirrors `zx_port_cancel`. Keys are caller-chosen and only unique per-binding, so
+    /// two different objects may share a key on the same port; scoping by object as well
+    /// as key keeps `cancel` from draining another object's still-undelivered packet.
+    /// Returns `ZxError::NOT_FOUND` if neither a registration nor a queued packet matched,
+    /// which lets callers reuse a key for a new subscription without racing an in-flight
+    /// packet.
+    pub fn cancel(&self, object: &Arc<dyn KernelObject>, key: u64) -> ZxResult {
+        let weak = Arc::downgrade(object);
+        let mut inner = self.inner.lock();
+
+        let waits_before = inner.async_waits.len();
+        inner
+            .async_waits
+            .retain(|wait| !(wait.key == key && Weak::ptr_eq(&wait.object, &weak)));
+        let removed_wait = inner.async_waits.len() != waits_before;
+
+        let packets_before = inner.queue.len();
+        inner.queue.retain(|item| {
+            let is_signal = matches!(item.packet.data, PortPacketPayload::Signal { .. });
+            let same_source = item
+                .source
+                .as_ref()
+                .is_some_and(|source| Weak::ptr_eq(source, &weak));
+            !(item.packet.key == key && same_source && is_signal)
+        });
+        let removed_packet = inner.queue.len() != packets_before;
+        if inner.queue.is_empty() {
+            self.base.signal_clear(Signal::READABLE);
+        }
+
+        if removed_wait || removed_packet {
+            Ok(())
+        } else {
+            Err(ZxError::NOT_FOUND)
+        }
     }
 
     /// Get the number of packets in queue.
@@ -81,7 +405,7 @@ mod tests {
     async fn wait_async() {
         let port = Port::new();
         let object = DummyObject::new() as Arc<dyn KernelObject>;
-        object.send_signal_to_port_async(Signal::READABLE, &port, 1);
+        object.send_signal_to_port_async(Signal::READABLE, &port, 1, WaitAsyncOpts::Once);
 
         async_std::task::spawn({
             let port = port.clone();
@@ -93,7 +417,12 @@ mod tests {
                 port.push(PortPacket {
                     key: 2,
                     status: ZxError::OK,
-                    data: PortPacketPayload::Signal(Signal::WRITABLE),
+                    priority: PRIO_NORMAL,
+                    data: PortPacketPayload::Signal {
+                        trigger: Signal::WRITABLE,
+                        observed: Signal::WRITABLE,
+                        count: 1,
+                    },
                 });
             }
         });
@@ -104,7 +433,12 @@ mod tests {
             [PortPacket {
                 key: 1,
                 status: ZxError::OK,
-                data: PortPacketPayload::Signal(Signal::READABLE),
+                priority: PRIO_NORMAL,
+                data: PortPacketPayload::Signal {
+                    trigger: Signal::READABLE,
+                    observed: Signal::READABLE,
+                    count: 1,
+                },
             }]
         );
 
@@ -114,8 +448,239 @@ mod tests {
             [PortPacket {
                 key: 2,
                 status: ZxError::OK,
-                data: PortPacketPayload::Signal(Signal::WRITABLE),
+                priority: PRIO_NORMAL,
+                data: PortPacketPayload::Signal {
+                    trigger: Signal::WRITABLE,
+                    observed: Signal::WRITABLE,
+                    count: 1,
+                },
             }]
         );
     }
+
+    #[async_std::test]
+    async fn wait_async_one() {
+        let port = Port::new();
+        port.push(PortPacket {
+            key: 1,
+            status: ZxError::OK,
+            priority: PRIO_NORMAL,
+            data: PortPacketPayload::Signal {
+                trigger: Signal::READABLE,
+                observed: Signal::READABLE,
+                count: 1,
+            },
+        });
+        port.push(PortPacket {
+            key: 2,
+            status: ZxError::OK,
+            priority: PRIO_NORMAL,
+            data: PortPacketPayload::Signal {
+                trigger: Signal::WRITABLE,
+                observed: Signal::WRITABLE,
+                count: 1,
+            },
+        });
+
+        // packets come back one at a time, in FIFO order
+        let packet = port.wait_async_one(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(packet.key, 1);
+        let packet = port.wait_async_one(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(packet.key, 2);
+
+        // queue is drained, so this should time out rather than hang
+        let err = port
+            .wait_async_one(Duration::from_millis(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err, ZxError::TIMED_OUT);
+    }
+
+    #[test]
+    fn async_wait_once_vs_repeating() {
+        let port = Port::new();
+        let object = DummyObject::new() as Arc<dyn KernelObject>;
+
+        port.push_async_wait(&object, 1, Signal::READABLE, WaitAsyncOpts::Once);
+        port.push_async_wait(&object, 2, Signal::READABLE, WaitAsyncOpts::Repeating);
+
+        port.on_signal_change(&object, Signal::READABLE);
+        assert_eq!(port.len(), 2);
+
+        // the `Once` wait already fired, so a second edge only touches the repeating
+        // one — and since its first packet is still undelivered, this coalesces into
+        // it rather than growing the queue
+        port.on_signal_change(&object, Signal::READABLE);
+        assert_eq!(port.len(), 2);
+
+        let inner = port.inner.lock();
+        let repeating = inner
+            .queue
+            .iter()
+            .find(|item| item.packet.key == 2)
+            .unwrap();
+        match &repeating.packet.data {
+            PortPacketPayload::Signal { count, .. } => assert_eq!(*count, 2),
+            _ => panic!("expected a Signal packet"),
+        }
+    }
+
+    #[test]
+    fn cancel() {
+        let port = Port::new();
+        let object = DummyObject::new() as Arc<dyn KernelObject>;
+
+        // cancelling a key with neither a registration nor a queued packet fails
+        assert_eq!(port.cancel(&object, 1).unwrap_err(), ZxError::NOT_FOUND);
+
+        // cancel removes a pending registration
+        port.push_async_wait(&object, 1, Signal::READABLE, WaitAsyncOpts::Repeating);
+        port.cancel(&object, 1).unwrap();
+        port.on_signal_change(&object, Signal::READABLE);
+        assert_eq!(port.len(), 0);
+
+        // cancel also drains an already-queued packet with that key
+        port.push_async_wait(&object, 2, Signal::READABLE, WaitAsyncOpts::Once);
+        port.on_signal_change(&object, Signal::READABLE);
+        assert_eq!(port.len(), 1);
+        port.cancel(&object, 2).unwrap();
+        assert_eq!(port.len(), 0);
+    }
+
+    #[test]
+    fn cancel_does_not_drain_another_objects_packet() {
+        // keys are caller-chosen per binding, not globally unique on a port, so two
+        // objects legitimately share a key (e.g. both using 0)
+        let port = Port::new();
+        let object_a = DummyObject::new() as Arc<dyn KernelObject>;
+        let object_b = DummyObject::new() as Arc<dyn KernelObject>;
+
+        port.push_async_wait(&object_a, 1, Signal::READABLE, WaitAsyncOpts::Once);
+        port.push_async_wait(&object_b, 1, Signal::READABLE, WaitAsyncOpts::Once);
+        port.on_signal_change(&object_a, Signal::READABLE);
+        port.on_signal_change(&object_b, Signal::READABLE);
+        assert_eq!(port.len(), 2);
+
+        // cancelling object_a's key 1 must not touch object_b's still-pending packet
+        port.cancel(&object_a, 1).unwrap();
+        assert_eq!(port.len(), 1);
+        assert_eq!(port.cancel(&object_a, 1).unwrap_err(), ZxError::NOT_FOUND);
+
+        port.cancel(&object_b, 1).unwrap();
+        assert_eq!(port.len(), 0);
+    }
+
+    #[test]
+    fn coalesce_repeating_signal() {
+        let port = Port::new();
+        let object = DummyObject::new() as Arc<dyn KernelObject>;
+        port.push_async_wait(&object, 1, Signal::READABLE, WaitAsyncOpts::Repeating);
+
+        // three edges while the packet sits undelivered leave one packet; the WRITABLE
+        // edge does not match the trigger, so only the two READABLE edges are counted
+        port.on_signal_change(&object, Signal::READABLE);
+        port.on_signal_change(&object, Signal::WRITABLE);
+        port.on_signal_change(&object, Signal::READABLE);
+        assert_eq!(port.len(), 1);
+
+        let inner = port.inner.lock();
+        match &inner.queue.peek().unwrap().packet.data {
+            PortPacketPayload::Signal {
+                observed, count, ..
+            } => {
+                assert_eq!(*observed, Signal::READABLE);
+                assert_eq!(*count, 2);
+            }
+            _ => panic!("expected a Signal packet"),
+        }
+    }
+
+    #[test]
+    fn coalesce_keeps_packets_from_shared_key_independent() {
+        // two objects sharing a key must never coalesce into each other's packet
+        let port = Port::new();
+        let object_a = DummyObject::new() as Arc<dyn KernelObject>;
+        let object_b = DummyObject::new() as Arc<dyn KernelObject>;
+        port.push_async_wait(&object_a, 1, Signal::READABLE, WaitAsyncOpts::Repeating);
+        port.push_async_wait(&object_b, 1, Signal::WRITABLE, WaitAsyncOpts::Repeating);
+
+        port.on_signal_change(&object_a, Signal::READABLE);
+        port.on_signal_change(&object_b, Signal::WRITABLE);
+        port.on_signal_change(&object_a, Signal::READABLE);
+        assert_eq!(port.len(), 2);
+
+        let inner = port.inner.lock();
+        for item in inner.queue.iter() {
+            match &item.packet.data {
+                PortPacketPayload::Signal {
+                    observed, count, ..
+                } => {
+                    if *observed == Signal::READABLE {
+                        assert_eq!(*count, 2, "object_a's packet should have coalesced twice");
+                    } else {
+                        assert_eq!(*observed, Signal::WRITABLE);
+                        assert_eq!(*count, 1, "object_b's packet must be untouched by object_a's edges");
+                    }
+                }
+                _ => panic!("expected a Signal packet"),
+            }
+        }
+    }
+
+    #[test]
+    fn priority_ordering() {
+        let port = Port::new();
+        port.push(PortPacket {
+            key: 1,
+            status: ZxError::OK,
+            priority: PRIO_BACKGROUND,
+            data: PortPacketPayload::User([0; 32]),
+        });
+        port.push(PortPacket {
+            key: 2,
+            status: ZxError::OK,
+            priority: PRIO_NORMAL,
+            data: PortPacketPayload::User([0; 32]),
+        });
+        port.push(PortPacket {
+            key: 3,
+            status: ZxError::OK,
+            priority: PRIO_NORMAL,
+            data: PortPacketPayload::User([0; 32]),
+        });
+        port.push(PortPacket {
+            key: 4,
+            status: ZxError::OK,
+            priority: PRIO_HIGH,
+            data: PortPacketPayload::User([0; 32]),
+        });
+
+        // PRIO_HIGH jumps the queue; equal priorities stay FIFO
+        assert_eq!(port.pop_one().unwrap().key, 4);
+        assert_eq!(port.pop_one().unwrap().key, 2);
+        assert_eq!(port.pop_one().unwrap().key, 3);
+        assert_eq!(port.pop_one().unwrap().key, 1);
+        assert!(port.pop_one().is_none());
+    }
+
+    #[test]
+    fn guest_trap() {
+        let port = Port::new();
+        port.push_guest_trap(1, PortPacketPayload::GuestBell { addr: 0x1000 });
+
+        let packet = port.pop_one().unwrap();
+        assert_eq!(packet.key, 1);
+        assert_eq!(packet.data, PortPacketPayload::GuestBell { addr: 0x1000 });
+    }
+
+    #[test]
+    fn interrupt_packet() {
+        let port = Port::new();
+        port.push_interrupt(1, 42);
+
+        let packet = port.pop_one().unwrap();
+        assert_eq!(packet.key, 1);
+        assert_eq!(packet.priority, PRIO_HIGH);
+        assert_eq!(packet.data, PortPacketPayload::Interrupt { timestamp: 42 });
+    }
 }